@@ -32,6 +32,9 @@ enum ErrorKind {
     InvalidInstruction(u8),
     Expected(usize),
     TrailingBytes,
+    InvalidTypeIndex(u32),
+    InvalidFuncIndex(u32),
+    ArgOutOfRange(u32),
 }
 
 impl<'a> Parser<'a> {
@@ -53,6 +56,12 @@ impl<'a> Parser<'a> {
         self.parse()
     }
 
+    /// Validates that every cross-reference in the remaining sections points at
+    /// a declared type or function. See [`Validator`] for the details.
+    pub fn validate(&self) -> Result<()> {
+        Validator::new().validate(self)
+    }
+
     fn parse<T: Parse<'a>>(&mut self) -> Result<T> {
         T::parse(self)
     }
@@ -80,6 +89,79 @@ pub trait Parse<'a>: Sized {
     fn parse(parser: &mut Parser<'a>) -> Result<Self>;
 }
 
+/// Serializes a structure back into the binary interface-types format.
+///
+/// This is the inverse of [`Parse`] and is implemented for everything that can
+/// be read out of a [`Parser`], allowing downstream tools to build up sections
+/// in memory and emit bytes that [`Parser::new`] will read back.
+pub trait Encode {
+    fn encode(&self, dst: &mut Vec<u8>) -> Result<()>;
+}
+
+/// Encodes a complete list of sections into a byte buffer, prefixed with the
+/// `wit_schema_version::VERSION` string just like [`Parser::new`] expects.
+pub fn encode<'a>(sections: impl IntoIterator<Item = Section<'a>>) -> Result<Vec<u8>> {
+    let mut dst = Vec::new();
+    wit_schema_version::VERSION.encode(&mut dst)?;
+    for section in sections {
+        section.encode(&mut dst)?;
+    }
+    Ok(dst)
+}
+
+impl<T: Encode + ?Sized> Encode for &'_ T {
+    fn encode(&self, dst: &mut Vec<u8>) -> Result<()> {
+        T::encode(self, dst)
+    }
+}
+
+impl<T: Encode + ?Sized> Encode for Box<T> {
+    fn encode(&self, dst: &mut Vec<u8>) -> Result<()> {
+        T::encode(self, dst)
+    }
+}
+
+impl<T: Encode> Encode for Option<T> {
+    fn encode(&self, dst: &mut Vec<u8>) -> Result<()> {
+        match self {
+            Some(val) => {
+                dst.push(1);
+                val.encode(dst)?;
+            }
+            None => dst.push(0),
+        }
+        Ok(())
+    }
+}
+
+impl Encode for u8 {
+    fn encode(&self, dst: &mut Vec<u8>) -> Result<()> {
+        dst.push(*self);
+        Ok(())
+    }
+}
+
+impl Encode for u32 {
+    fn encode(&self, dst: &mut Vec<u8>) -> Result<()> {
+        leb128::write::unsigned(dst, u64::from(*self)).unwrap();
+        Ok(())
+    }
+}
+
+impl Encode for [u8] {
+    fn encode(&self, dst: &mut Vec<u8>) -> Result<()> {
+        (self.len() as u32).encode(dst)?;
+        dst.extend_from_slice(self);
+        Ok(())
+    }
+}
+
+impl Encode for str {
+    fn encode(&self, dst: &mut Vec<u8>) -> Result<()> {
+        self.as_bytes().encode(dst)
+    }
+}
+
 pub enum Section<'a> {
     Type(Types<'a>),
     Import(Imports<'a>),
@@ -121,6 +203,27 @@ impl<'a> Parse<'a> for Section<'a> {
     }
 }
 
+impl Encode for Section<'_> {
+    fn encode(&self, dst: &mut Vec<u8>) -> Result<()> {
+        fn body<T: Encode>(cnt: u32, items: impl Iterator<Item = Result<T>>) -> Result<Vec<u8>> {
+            let mut body = Vec::new();
+            cnt.encode(&mut body)?;
+            for item in items {
+                item?.encode(&mut body)?;
+            }
+            Ok(body)
+        }
+        let (id, body) = match self {
+            Section::Type(s) => (0, body(s.cnt, s.clone())?),
+            Section::Import(s) => (1, body(s.cnt, s.clone())?),
+            Section::Export(s) => (2, body(s.cnt, s.clone())?),
+            Section::Func(s) => (3, body(s.cnt, s.clone())?),
+        };
+        dst.push(id);
+        body.encode(dst)
+    }
+}
+
 impl<'a> Parse<'a> for u8 {
     fn parse(parser: &mut Parser<'a>) -> Result<Self> {
         match parser.bytes.get(0).cloned() {
@@ -176,27 +279,31 @@ impl<'a> Parse<'a> for u32 {
     }
 }
 
+#[derive(Clone)]
 pub struct Types<'a> {
     parser: Parser<'a>,
     cnt: u32,
 }
 
 impl<'a> Iterator for Types<'a> {
-    type Item = Result<Type>;
+    type Item = Result<Type<'a>>;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.parser.parse_next_in_section(&mut self.cnt)
     }
 }
 
-pub struct Type {
-    pub params: Vec<ValType>,
-    pub results: Vec<ValType>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Type<'a> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub params: Vec<ValType<'a>>,
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub results: Vec<ValType<'a>>,
 }
 
-impl<'a> Parse<'a> for Type {
-    fn parse(parser: &mut Parser<'a>) -> Result<Type> {
-        let mut types = || -> Result<Vec<ValType>> {
+impl<'a> Parse<'a> for Type<'a> {
+    fn parse(parser: &mut Parser<'a>) -> Result<Type<'a>> {
+        let mut types = || -> Result<Vec<ValType<'a>>> {
             let cnt = parser.parse::<u32>()?;
             (0..cnt).map(|_| parser.parse()).collect()
         };
@@ -207,7 +314,22 @@ impl<'a> Parse<'a> for Type {
     }
 }
 
-pub enum ValType {
+impl Encode for Type<'_> {
+    fn encode(&self, dst: &mut Vec<u8>) -> Result<()> {
+        let types = |tys: &[ValType<'_>], dst: &mut Vec<u8>| -> Result<()> {
+            (tys.len() as u32).encode(dst)?;
+            for ty in tys {
+                ty.encode(dst)?;
+            }
+            Ok(())
+        };
+        types(&self.params, dst)?;
+        types(&self.results, dst)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ValType<'a> {
     S8,
     S16,
     S32,
@@ -219,10 +341,19 @@ pub enum ValType {
     F32,
     F64,
     String,
-}
-
-impl<'a> Parse<'a> for ValType {
-    fn parse(parser: &mut Parser<'a>) -> Result<ValType> {
+    List(#[cfg_attr(feature = "serde", serde(borrow))] Box<ValType<'a>>),
+    Record(#[cfg_attr(feature = "serde", serde(borrow))] Vec<(Option<&'a str>, ValType<'a>)>),
+    Variant(#[cfg_attr(feature = "serde", serde(borrow))] Vec<(&'a str, Option<ValType<'a>>)>),
+    Option(#[cfg_attr(feature = "serde", serde(borrow))] Box<ValType<'a>>),
+    Result {
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        ok: Option<Box<ValType<'a>>>,
+        err: Option<Box<ValType<'a>>>,
+    },
+}
+
+impl<'a> Parse<'a> for ValType<'a> {
+    fn parse(parser: &mut Parser<'a>) -> Result<ValType<'a>> {
         Ok(match parser.parse::<u8>()? {
             0 => ValType::String,
             1 => ValType::S8,
@@ -235,11 +366,91 @@ impl<'a> Parse<'a> for ValType {
             8 => ValType::U64,
             9 => ValType::F32,
             10 => ValType::F64,
+            11 => ValType::List(Box::new(parser.parse()?)),
+            12 => {
+                let cnt = parser.parse::<u32>()?;
+                ValType::Record(
+                    (0..cnt)
+                        .map(|_| Ok((parse_opt(parser)?, parser.parse()?)))
+                        .collect::<Result<_>>()?,
+                )
+            }
+            13 => {
+                let cnt = parser.parse::<u32>()?;
+                ValType::Variant(
+                    (0..cnt)
+                        .map(|_| Ok((parser.parse()?, parse_opt(parser)?)))
+                        .collect::<Result<_>>()?,
+                )
+            }
+            14 => ValType::Option(Box::new(parser.parse()?)),
+            15 => ValType::Result {
+                ok: parse_opt(parser)?.map(Box::new),
+                err: parse_opt(parser)?.map(Box::new),
+            },
             n => return Err(parser.error(ErrorKind::InvalidValType(n))),
         })
     }
 }
 
+/// Parses an optionally-present value, encoded as a presence byte followed by
+/// the value itself when the byte is nonzero.
+fn parse_opt<'a, T: Parse<'a>>(parser: &mut Parser<'a>) -> Result<Option<T>> {
+    Ok(match parser.parse::<u8>()? {
+        0 => None,
+        _ => Some(parser.parse()?),
+    })
+}
+
+impl Encode for ValType<'_> {
+    fn encode(&self, dst: &mut Vec<u8>) -> Result<()> {
+        match self {
+            ValType::String => dst.push(0),
+            ValType::S8 => dst.push(1),
+            ValType::S16 => dst.push(2),
+            ValType::S32 => dst.push(3),
+            ValType::S64 => dst.push(4),
+            ValType::U8 => dst.push(5),
+            ValType::U16 => dst.push(6),
+            ValType::U32 => dst.push(7),
+            ValType::U64 => dst.push(8),
+            ValType::F32 => dst.push(9),
+            ValType::F64 => dst.push(10),
+            ValType::List(ty) => {
+                dst.push(11);
+                ty.encode(dst)?;
+            }
+            ValType::Record(fields) => {
+                dst.push(12);
+                (fields.len() as u32).encode(dst)?;
+                for (name, ty) in fields {
+                    name.encode(dst)?;
+                    ty.encode(dst)?;
+                }
+            }
+            ValType::Variant(cases) => {
+                dst.push(13);
+                (cases.len() as u32).encode(dst)?;
+                for (name, ty) in cases {
+                    name.encode(dst)?;
+                    ty.encode(dst)?;
+                }
+            }
+            ValType::Option(ty) => {
+                dst.push(14);
+                ty.encode(dst)?;
+            }
+            ValType::Result { ok, err } => {
+                dst.push(15);
+                ok.encode(dst)?;
+                err.encode(dst)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
 pub struct Imports<'a> {
     parser: Parser<'a>,
     cnt: u32,
@@ -253,6 +464,7 @@ impl<'a> Iterator for Imports<'a> {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Import<'a> {
     pub module: &'a str,
     pub name: &'a str,
@@ -269,6 +481,15 @@ impl<'a> Parse<'a> for Import<'a> {
     }
 }
 
+impl Encode for Import<'_> {
+    fn encode(&self, dst: &mut Vec<u8>) -> Result<()> {
+        self.module.encode(dst)?;
+        self.name.encode(dst)?;
+        self.ty.encode(dst)
+    }
+}
+
+#[derive(Clone)]
 pub struct Exports<'a> {
     parser: Parser<'a>,
     cnt: u32,
@@ -282,6 +503,7 @@ impl<'a> Iterator for Exports<'a> {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Export<'a> {
     pub func: u32,
     pub name: &'a str,
@@ -296,6 +518,14 @@ impl<'a> Parse<'a> for Export<'a> {
     }
 }
 
+impl Encode for Export<'_> {
+    fn encode(&self, dst: &mut Vec<u8>) -> Result<()> {
+        self.func.encode(dst)?;
+        self.name.encode(dst)
+    }
+}
+
+#[derive(Clone)]
 pub struct Funcs<'a> {
     parser: Parser<'a>,
     cnt: u32,
@@ -336,6 +566,19 @@ impl<'a> Func<'a> {
     }
 }
 
+impl Encode for Func<'_> {
+    fn encode(&self, dst: &mut Vec<u8>) -> Result<()> {
+        let mut body = Vec::new();
+        self.ty.encode(&mut body)?;
+        for instr in self.instrs() {
+            instr?.encode(&mut body)?;
+        }
+        // `instrs` stops at the terminating `end`, so re-emit it here.
+        Instruction::End.encode(&mut body)?;
+        body.encode(dst)
+    }
+}
+
 pub struct Instructions<'a> {
     parser: Parser<'a>,
 }
@@ -360,12 +603,13 @@ impl<'a> Iterator for Instructions<'a> {
 macro_rules! instructions {
     (pub enum Instruction {
         $(
-            $name:ident $(($($arg:tt)*))? = $binary:tt,
+            $name:ident $(( $($arg:ident : $argty:ty),* ))? = $binary:tt,
         )*
     }) => (
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         pub enum Instruction {
             $(
-                $name $(( $($arg)* ))?,
+                $name $(( $($argty),* ))?,
             )*
         }
 
@@ -375,7 +619,7 @@ macro_rules! instructions {
                 $(
                     fn $name(_parser: &mut Parser<'_>) -> Result<Instruction> {
                         Ok(Instruction::$name $((
-                            _parser.parse::<$($arg)*>()?,
+                            $( _parser.parse::<$argty>()?, )*
                         ))?)
                     }
                 )*
@@ -391,14 +635,183 @@ macro_rules! instructions {
                 }
             }
         }
+
+        impl Encode for Instruction {
+            fn encode(&self, dst: &mut Vec<u8>) -> Result<()> {
+                match self {
+                    $(
+                        Instruction::$name $(( $($arg),* ))? => {
+                            dst.push($binary);
+                            $( $( $arg.encode(dst)?; )* )?
+                        }
+                    )*
+                }
+                Ok(())
+            }
+        }
     );
 }
 
 instructions! {
     pub enum Instruction {
-        ArgGet(u32) = 0x00,
-        CallCore(u32) = 0x01,
+        ArgGet(idx: u32) = 0x00,
+        CallCore(func: u32) = 0x01,
         End = 0x02,
+
+        I32ToS8 = 0x03,
+        I32ToU8 = 0x04,
+        I32ToS16 = 0x05,
+        I32ToU16 = 0x06,
+        I32ToS32 = 0x07,
+        I32ToU32 = 0x08,
+        I64ToS64 = 0x09,
+        I64ToU64 = 0x0a,
+
+        S8ToI32 = 0x0b,
+        U8ToI32 = 0x0c,
+        S16ToI32 = 0x0d,
+        U16ToI32 = 0x0e,
+        S32ToI32 = 0x0f,
+        U32ToI32 = 0x10,
+        S64ToI64 = 0x11,
+        U64ToI64 = 0x12,
+
+        MemoryToString(mem: u32) = 0x13,
+        StringToMemory(malloc: u32, mem: u32) = 0x14,
+    }
+}
+
+/// Semantic validator for a parsed interface-types payload.
+///
+/// [`Parser`] only verifies byte-level structure, so a `Func` may still name a
+/// nonexistent type or an `Export` a missing function. A `Validator` walks the
+/// section iterators once, building up the declared type and function tables,
+/// and then checks that every cross-reference points at something real.
+#[derive(Default)]
+pub struct Validator {
+    /// Parameter count of each declared type, indexed by type index.
+    type_params: Vec<u32>,
+    /// References that must name a valid type index.
+    type_refs: Vec<Ref>,
+    /// Defined functions, with their `arg.get` references.
+    funcs: Vec<FuncRef>,
+    /// References that must name a valid function index.
+    func_refs: Vec<Ref>,
+    /// Number of imported functions, which precede the defined ones.
+    imported_funcs: u32,
+}
+
+/// An index reference plus the byte position it was parsed from, used to point
+/// validation errors back at the offending bytes.
+struct Ref {
+    index: u32,
+    at: usize,
+}
+
+/// A defined function awaiting validation: its type index and the `arg.get`
+/// references whose indices must fall within that type's parameters.
+struct FuncRef {
+    ty: Ref,
+    args: Vec<Ref>,
+}
+
+impl Validator {
+    pub fn new() -> Validator {
+        Validator::default()
+    }
+
+    /// Validates the sections produced by `parser`, consuming a clone of it.
+    pub fn validate(mut self, parser: &Parser<'_>) -> Result<()> {
+        let mut parser = parser.clone();
+        while !parser.is_empty() {
+            match parser.section()? {
+                Section::Type(types) => {
+                    for ty in types {
+                        self.type_params.push(ty?.params.len() as u32);
+                    }
+                }
+                Section::Import(mut imports) => loop {
+                    let at = imports.parser.pos;
+                    match imports.next() {
+                        Some(import) => {
+                            self.type_refs.push(Ref {
+                                index: import?.ty,
+                                at,
+                            });
+                            self.imported_funcs += 1;
+                        }
+                        None => break,
+                    }
+                },
+                Section::Export(mut exports) => loop {
+                    let at = exports.parser.pos;
+                    match exports.next() {
+                        Some(export) => self.func_refs.push(Ref {
+                            index: export?.func,
+                            at,
+                        }),
+                        None => break,
+                    }
+                },
+                Section::Func(mut funcs) => loop {
+                    let at = funcs.parser.pos;
+                    match funcs.next() {
+                        Some(func) => {
+                            let func = func?;
+                            let mut args = Vec::new();
+                            let mut instrs = func.instrs();
+                            loop {
+                                let at = instrs.parser.pos;
+                                match instrs.next() {
+                                    Some(instr) => {
+                                        if let Instruction::ArgGet(idx) = instr? {
+                                            args.push(Ref { index: idx, at });
+                                        }
+                                    }
+                                    None => break,
+                                }
+                            }
+                            self.funcs.push(FuncRef {
+                                ty: Ref { index: func.ty, at },
+                                args,
+                            });
+                        }
+                        None => break,
+                    }
+                },
+            }
+        }
+
+        let types = self.type_params.len() as u32;
+        for r in self.type_refs.iter() {
+            if r.index >= types {
+                return Err(error(r.at, ErrorKind::InvalidTypeIndex(r.index)));
+            }
+        }
+        let funcs = self.imported_funcs + self.funcs.len() as u32;
+        for r in self.func_refs.iter() {
+            if r.index >= funcs {
+                return Err(error(r.at, ErrorKind::InvalidFuncIndex(r.index)));
+            }
+        }
+        for func in self.funcs.iter() {
+            if func.ty.index >= types {
+                return Err(error(func.ty.at, ErrorKind::InvalidTypeIndex(func.ty.index)));
+            }
+            let params = self.type_params[func.ty.index as usize];
+            for arg in func.args.iter() {
+                if arg.index >= params {
+                    return Err(error(arg.at, ErrorKind::ArgOutOfRange(arg.index)));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn error(at: usize, kind: ErrorKind) -> Error {
+    Error {
+        inner: Box::new(ErrorInner { at, kind }),
     }
 }
 
@@ -421,8 +834,153 @@ impl fmt::Display for Error {
             ErrorKind::InvalidInstruction(n) => write!(f, "invalid instruction: {}", n),
             ErrorKind::Expected(n) => write!(f, "expected {} more bytes but hit eof", n),
             ErrorKind::TrailingBytes => write!(f, "trailing bytes at the end of the section"),
+            ErrorKind::InvalidTypeIndex(n) => write!(f, "type index {} is out of bounds", n),
+            ErrorKind::InvalidFuncIndex(n) => write!(f, "function index {} is out of bounds", n),
+            ErrorKind::ArgOutOfRange(n) => {
+                write!(f, "argument index {} exceeds the type's parameters", n)
+            }
         }
     }
 }
 
 impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Assembles a module: the version prefix followed by `(id, body)` sections
+    /// with the same framing `Section::parse` expects.
+    fn module(sections: &[(u8, &[u8])]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        wit_schema_version::VERSION.encode(&mut bytes).unwrap();
+        for &(id, body) in sections {
+            bytes.push(id);
+            body.encode(&mut bytes).unwrap();
+        }
+        bytes
+    }
+
+    #[test]
+    fn round_trip() {
+        // one type `(s32) -> string`, one import of it, one export, and one
+        // function that references argument 0 and then ends.
+        let bytes = module(&[
+            (0, &[1, 1, 3, 1, 0]),
+            (1, &[1, 1, b'm', 1, b'f', 0]),
+            (2, &[1, 0, 1, b'e']),
+            (3, &[1, 4, 0, 0, 0, 2]),
+        ]);
+        let mut parser = Parser::new(&bytes).unwrap();
+        let mut sections = Vec::new();
+        while !parser.is_empty() {
+            sections.push(parser.section().unwrap());
+        }
+        assert_eq!(encode(sections).unwrap(), bytes);
+    }
+
+    #[test]
+    fn validate_ok() {
+        let bytes = module(&[
+            (0, &[1, 1, 3, 1, 0]),
+            (1, &[1, 1, b'm', 1, b'f', 0]),
+            (2, &[1, 0, 1, b'e']),
+            (3, &[1, 4, 0, 0, 0, 2]),
+        ]);
+        Parser::new(&bytes).unwrap().validate().unwrap();
+    }
+
+    #[test]
+    fn validate_bad_type_index() {
+        // one type declared, but the function references type index 5.
+        let bytes = module(&[(0, &[1, 0, 0]), (3, &[1, 2, 5, 2])]);
+        let err = Parser::new(&bytes).unwrap().validate().unwrap_err();
+        assert!(matches!(err.inner.kind, ErrorKind::InvalidTypeIndex(5)));
+    }
+
+    #[test]
+    fn validate_bad_func_index() {
+        // an export of function 7 with no functions defined or imported.
+        let bytes = module(&[(2, &[1, 7, 1, b'e'])]);
+        let err = Parser::new(&bytes).unwrap().validate().unwrap_err();
+        assert!(matches!(err.inner.kind, ErrorKind::InvalidFuncIndex(7)));
+    }
+
+    #[test]
+    fn validate_arg_out_of_range() {
+        // a type with no params, but the function reads argument 3.
+        let bytes = module(&[(0, &[1, 0, 0]), (3, &[1, 4, 0, 0, 3, 2])]);
+        let err = Parser::new(&bytes).unwrap().validate().unwrap_err();
+        assert!(matches!(err.inner.kind, ErrorKind::ArgOutOfRange(3)));
+    }
+}
+
+#[cfg(feature = "serde")]
+pub use self::document::{document, Document};
+
+/// Collected, serializable mirror of a parsed module.
+///
+/// The streaming [`Parser`] and its [`Section`]/[`Func`] iterators borrow a
+/// live cursor and so can't be serialized directly. When the `serde` feature is
+/// enabled this module collects all sections into structures that hold no
+/// parser and can be dumped to JSON, CBOR, or any other `serde` format for
+/// debugging, snapshot tests, or interchange with non-Rust tools. Names and
+/// other strings in them still borrow from the input bytes.
+#[cfg(feature = "serde")]
+mod document {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    /// Every section of a module, collected eagerly.
+    #[derive(Serialize, Deserialize)]
+    pub struct Document<'a> {
+        #[serde(borrow)]
+        pub sections: Vec<Section<'a>>,
+    }
+
+    /// Collected counterpart of [`crate::Section`].
+    #[derive(Serialize, Deserialize)]
+    pub enum Section<'a> {
+        Type(#[serde(borrow)] Vec<Type<'a>>),
+        Import(#[serde(borrow)] Vec<Import<'a>>),
+        Export(#[serde(borrow)] Vec<Export<'a>>),
+        Func(Vec<Func>),
+    }
+
+    /// Owned counterpart of [`crate::Func`], with its instructions collected up
+    /// front rather than left behind a live parser.
+    #[derive(Serialize, Deserialize)]
+    pub struct Func {
+        pub ty: u32,
+        pub instrs: Vec<Instruction>,
+    }
+
+    /// Walks `parser` and collects every section into a [`Document`].
+    pub fn document<'a>(parser: &Parser<'a>) -> Result<Document<'a>> {
+        let mut parser = parser.clone();
+        let mut sections = Vec::new();
+        while !parser.is_empty() {
+            sections.push(match parser.section()? {
+                crate::Section::Type(tys) => Section::Type(tys.collect::<Result<_>>()?),
+                crate::Section::Import(imports) => {
+                    Section::Import(imports.collect::<Result<_>>()?)
+                }
+                crate::Section::Export(exports) => {
+                    Section::Export(exports.collect::<Result<_>>()?)
+                }
+                crate::Section::Func(funcs) => Section::Func(
+                    funcs
+                        .map(|func| {
+                            let func = func?;
+                            Ok(Func {
+                                ty: func.ty,
+                                instrs: func.instrs().collect::<Result<_>>()?,
+                            })
+                        })
+                        .collect::<Result<_>>()?,
+                ),
+            });
+        }
+        Ok(Document { sections })
+    }
+}
@@ -3,12 +3,12 @@ use wast::parser::{Parse, Parser, Result};
 macro_rules! instructions {
     (pub enum Instruction<'a> {
         $(
-            $name:ident $(($($arg:tt)*))? : [$($binary:tt)*] : $instr:tt,
+            $name:ident $(( $($arg:ident : $argty:ty),* ))? : [$($binary:tt)*] : $instr:tt,
         )*
     }) => (
         pub enum Instruction<'a> {
             $(
-                $name $(( $($arg)* ))?,
+                $name $(( $($argty),* ))?,
             )*
         }
 
@@ -18,7 +18,7 @@ macro_rules! instructions {
                 $(
                     fn $name<'a>(_parser: Parser<'a>) -> Result<Instruction<'a>> {
                         Ok(Instruction::$name $((
-                            _parser.parse::<$($arg)*>()?,
+                            $( _parser.parse::<$argty>()?, )*
                         ))?)
                     }
                 )*
@@ -37,30 +37,45 @@ macro_rules! instructions {
         }
 
         impl crate::binary::Encode for Instruction<'_> {
-            #[allow(non_snake_case)]
             fn encode(&self, v: &mut Vec<u8>) {
                 match self {
                     $(
-                        Instruction::$name $((instructions!(@first $($arg)*)))? => {
-                            fn encode<'a>($(arg: &$($arg)*,)? v: &mut Vec<u8>) {
-                                v.extend_from_slice(&[$($binary)*]);
-                                $(<$($arg)* as crate::binary::Encode>::encode(arg, v);)?
-                            }
-                            encode($( instructions!(@first $($arg)*), )? v)
+                        Instruction::$name $(( $($arg),* ))? => {
+                            v.extend_from_slice(&[$($binary)*]);
+                            $( $( crate::binary::Encode::encode($arg, v); )* )?
                         }
                     )*
                 }
             }
         }
     );
-
-    (@first $first:ident $($t:tt)*) => ($first);
 }
 
 instructions! {
     pub enum Instruction<'a> {
-        ArgGet(wast::Index<'a>) : [0x00] : "arg.get",
-        CallCore(wast::Index<'a>) : [0x01] : "call-core",
+        ArgGet(idx: wast::Index<'a>) : [0x00] : "arg.get",
+        CallCore(func: wast::Index<'a>) : [0x01] : "call-core",
         End : [0x02] : "end",
+
+        I32ToS8 : [0x03] : "i32-to-s8",
+        I32ToU8 : [0x04] : "i32-to-u8",
+        I32ToS16 : [0x05] : "i32-to-s16",
+        I32ToU16 : [0x06] : "i32-to-u16",
+        I32ToS32 : [0x07] : "i32-to-s32",
+        I32ToU32 : [0x08] : "i32-to-u32",
+        I64ToS64 : [0x09] : "i64-to-s64",
+        I64ToU64 : [0x0a] : "i64-to-u64",
+
+        S8ToI32 : [0x0b] : "s8-to-i32",
+        U8ToI32 : [0x0c] : "u8-to-i32",
+        S16ToI32 : [0x0d] : "s16-to-i32",
+        U16ToI32 : [0x0e] : "u16-to-i32",
+        S32ToI32 : [0x0f] : "s32-to-i32",
+        U32ToI32 : [0x10] : "u32-to-i32",
+        S64ToI64 : [0x11] : "s64-to-i64",
+        U64ToI64 : [0x12] : "u64-to-i64",
+
+        MemoryToString(mem: wast::Index<'a>) : [0x13] : "memory-to-string",
+        StringToMemory(malloc: wast::Index<'a>, mem: wast::Index<'a>) : [0x14] : "string-to-memory",
     }
 }